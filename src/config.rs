@@ -0,0 +1,71 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE_NAME: &str = "act.json";
+const DEFAULT_DATA_PATH: &str = "act.jsonl";
+const CONFIG_VERSION: u32 = 1;
+
+/// Per-project `act.json` config, located by walking up from the current
+/// directory like a dotfile lookup. Holds at least the path to the project
+/// log so data can live wherever a project wants instead of always being
+/// `act.jsonl` in the CWD.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Config {
+    pub data_path: String,
+    pub version: u32,
+}
+
+impl Config {
+    /// Searches the current directory and its ancestors for `act.json`.
+    fn find() -> Result<Option<(PathBuf, Config)>> {
+        let mut dir = env::current_dir()?;
+        loop {
+            let candidate = dir.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                let contents = fs::read_to_string(&candidate)
+                    .with_context(|| format!("reading {}", candidate.display()))?;
+                let config: Config = serde_json::from_str(&contents)
+                    .with_context(|| format!("parsing {}", candidate.display()))?;
+                return Ok(Some((candidate, config)));
+            }
+            if !dir.pop() {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Resolves the data log path from the nearest `act.json`, falling back
+    /// to `act.jsonl` in the current directory when no config is found. A
+    /// relative `data_path` is resolved against the directory the config
+    /// was found in (not the CWD), so the same project resolves to the same
+    /// file regardless of which subdirectory `act` is run from.
+    pub fn resolve_data_path() -> Result<PathBuf> {
+        match Self::find()? {
+            Some((config_path, config)) => {
+                let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+                Ok(config_dir.join(config.data_path))
+            }
+            None => Ok(PathBuf::from(DEFAULT_DATA_PATH)),
+        }
+    }
+
+    /// Writes a new `act.json` in the current directory, refusing to
+    /// overwrite a config that already exists in this directory or an
+    /// ancestor.
+    pub fn init(data_path: &str) -> Result<PathBuf> {
+        if let Some((path, _)) = Self::find()? {
+            bail!("config already exists at {}", path.display());
+        }
+        let path = env::current_dir()?.join(CONFIG_FILE_NAME);
+        let config = Config {
+            data_path: data_path.to_string(),
+            version: CONFIG_VERSION,
+        };
+        fs::write(&path, serde_json::to_string_pretty(&config)?)
+            .with_context(|| format!("writing {}", path.display()))?;
+        Ok(path)
+    }
+}
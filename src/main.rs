@@ -1,160 +1,194 @@
-use anyhow::Result;
-use clap::Parser;
-use serde::{Deserialize, Serialize};
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, BufWriter};
-use std::path::Path;
-
-/// type Project = {
-///   name: string
-///   status: 'active' | 'inactive' | 'archived'
-///   focus: number
-/// }
-
-#[derive(Serialize, Deserialize, Debug)]
-enum Status {
-    Active,
-    Inactive,
-    Archived,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Project {
-    name: String,
-    status: Status,
-    focus: u8, // 0-100
-}
-
-/// Simple program to greet a person
+mod config;
+mod project;
+mod repository;
+mod session;
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use config::Config;
+use project::{Project, Status};
+use repository::{FsRepository, Repository};
+use session::{derive_sessions_path, format_duration, SessionStore};
+
+/// A focus/project tracker backed by an append-only event log.
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Name of the person to greet
-    #[arg(short, long)]
-    name: String,
-}
-
-fn find_project_by_name<'a>(projects: &'a [Project], query: &str) -> Option<&'a Project> {
-    projects.iter().find(|p| p.name == query)
-}
-
-fn read_projects_from_file(file_path: &str) -> Result<Vec<Project>> {
-    // Open the file in read-only mode
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
-    // Deserialize the JSON data into a Vec<Project>
-    let projects = serde_json::from_reader(reader)?;
-    Ok(projects)
-}
-
-fn write_to_json(projects: &[Project], path: &Path) -> Result<()> {
-    let file = File::create(path)?; // `?` will automatically convert std::io::Error to anyhow::Error
-    serde_json::to_writer(file, projects)?; // Same here for serde_json::Error
-    Ok(())
+    #[command(subcommand)]
+    command: Command,
 }
 
-fn write_projects_to_file(projects: &[Project], file_path: &str) -> Result<()> {
-    let file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(file_path)?;
-    let writer = BufWriter::new(file);
-    serde_json::to_writer_pretty(writer, projects)?;
-    Ok(())
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Initialize an act.json config in the current directory
+    Init {
+        #[arg(long, default_value = "act.jsonl")]
+        data_path: String,
+    },
+    /// Add a new project
+    Add {
+        name: String,
+        #[arg(long, value_enum, default_value = "active")]
+        status: Status,
+        #[arg(long, default_value_t = 0)]
+        focus: u8,
+    },
+    /// List projects, optionally filtered by status, sorted by focus descending
+    List {
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+    },
+    /// Change a project's status
+    SetStatus { name: String, status: Status },
+    /// Set a project's focus (0-100)
+    Focus { name: String, value: u8 },
+    /// Archive a project
+    Archive { name: String },
+    /// Start a focus session for a project
+    Start {
+        name: String,
+        /// Stop the currently active session first, if any
+        #[arg(long)]
+        switch: bool,
+    },
+    /// Stop the currently active focus session
+    Stop,
+    /// Show the currently active project and its running time
+    Status,
+    /// List past focus sessions for a project
+    History { name: String },
+    /// Collapse the project log down to its current state
+    Compact,
 }
 
-fn update_or_add_project(projects: &mut Vec<Project>, new_project: Project) {
-    match projects.iter_mut().find(|p| p.name == new_project.name) {
-        Some(project) => {
-            // Update the existing project
-            *project = new_project;
-        }
-        None => {
-            // Add new project since it does not exist
-            projects.push(new_project);
+fn run(command: Command, repo: &impl Repository, sessions: &SessionStore) -> Result<()> {
+    match command {
+        Command::Init { .. } => unreachable!("Init is handled in main before a repository exists"),
+        Command::Compact => unreachable!("Compact is handled in main, where the concrete FsRepository is available"),
+        Command::Add {
+            name,
+            status,
+            focus,
+        } => {
+            if focus > 100 {
+                bail!("focus must be between 0 and 100, got {focus}");
+            }
+            if repo.find_by_name(&name)?.is_some() {
+                bail!("project '{name}' already exists");
+            }
+            repo.upsert(Project {
+                name,
+                status,
+                focus,
+            })?;
         }
-    }
-}
-
-fn main() -> Result<()> {
-    let projects = vec![
-        Project {
-            name: "one".to_string(),
-            status: Status::Active,
-            focus: 100,
-        },
-        Project {
-            name: "two".to_string(),
-            status: Status::Archived,
-            focus: 75,
-        },
-    ];
-
-    let file_path = "projects.json";
-    let path = Path::new(file_path);
-
-    // WRITE
-    write_to_json(&projects, path).expect("Failed to write to file");
-
-    // FIND ONE
-    let search_name = "two";
-    match find_project_by_name(&projects, search_name) {
-        Some(project) => println!(
-            "Found project: {:?}, Status: {:?}, Focus: {}",
-            project.name, project.status, project.focus
-        ),
-        None => println!("Project not found."),
-    }
-
-    // GET ALL
-    match read_projects_from_file(file_path) {
-        Ok(projects) => {
+        Command::List { status } => {
+            let mut projects = repo.all()?;
+            match status {
+                Some(status) => projects.retain(|p| p.status == status),
+                // Archived projects are hidden unless explicitly asked for.
+                None => projects.retain(|p| p.status != Status::Archived),
+            }
+            projects.sort_by_key(|p| std::cmp::Reverse(p.focus));
+            let all_sessions = sessions.all()?;
             for project in projects {
                 println!(
-                    "Project Name: {}, Status: {:?}, Focus: {}",
-                    project.name, project.status, project.focus
+                    "{}\t{:?}\t{}\t{}",
+                    project.name,
+                    project.status,
+                    project.focus,
+                    format_duration(project.total_focus_time(&all_sessions))
                 );
             }
         }
-        Err(e) => {
-            println!("Failed to read projects: {}", e);
+        Command::SetStatus { name, status } => {
+            let mut project = repo
+                .find_by_name(&name)?
+                .ok_or_else(|| anyhow::anyhow!("project '{name}' not found"))?;
+            project.status = status;
+            repo.upsert(project)?;
+        }
+        Command::Focus { name, value } => {
+            if value > 100 {
+                bail!("focus must be between 0 and 100, got {value}");
+            }
+            let mut project = repo
+                .find_by_name(&name)?
+                .ok_or_else(|| anyhow::anyhow!("project '{name}' not found"))?;
+            project.focus = value;
+            repo.upsert(project)?;
+        }
+        Command::Archive { name } => {
+            if repo.find_by_name(&name)?.is_none() {
+                bail!("project '{name}' not found");
+            }
+            repo.remove(&name)?;
+        }
+        Command::Start { name, switch } => {
+            match repo.find_by_name(&name)? {
+                None => bail!("project '{name}' not found"),
+                Some(project) if project.status == Status::Archived => {
+                    bail!("project '{name}' is archived")
+                }
+                Some(_) => {}
+            }
+            sessions.start(&name, switch)?;
+        }
+        Command::Stop => match sessions.stop()? {
+            Some(session) => println!(
+                "Stopped '{}' after {}",
+                session.name,
+                format_duration(session.duration())
+            ),
+            None => println!("No active session"),
+        },
+        Command::Status => match sessions.current()? {
+            Some(session) => println!(
+                "Active: '{}' for {}",
+                session.name,
+                format_duration(session.duration())
+            ),
+            None => println!("No active session"),
+        },
+        Command::History { name } => {
+            for session in sessions.sessions_for(&name)? {
+                match session.ended_at {
+                    Some(ended_at) => println!(
+                        "{} -> {} ({})",
+                        session.started_at,
+                        ended_at,
+                        format_duration(session.duration())
+                    ),
+                    None => println!(
+                        "{} -> running ({})",
+                        session.started_at,
+                        format_duration(session.duration())
+                    ),
+                }
+            }
         }
     }
+    Ok(())
+}
 
-    // UPDATE
-    // Read the existing projects
-    let mut projects = read_projects_from_file(file_path)?;
-
-    // Create a new project or modify an existing project's data
-    let updated_project = Project {
-        name: "two".to_string(),
-        status: Status::Active,
-        focus: 33, // Updated focus or any other fields
-    };
+fn main() -> Result<()> {
+    let args = Args::parse();
 
-    // Update the project list
-    update_or_add_project(&mut projects, updated_project);
+    if let Command::Init { data_path } = args.command {
+        let path = Config::init(&data_path)?;
+        println!("Wrote config to {}", path.display());
+        return Ok(());
+    }
 
-    // Write the updated list back to the file
-    write_projects_to_file(&projects, file_path)?;
+    let data_path = Config::resolve_data_path()?;
+    let sessions = SessionStore::new(derive_sessions_path(&data_path));
+    let repo = FsRepository::new(data_path);
 
-    // GET ALL AGAIN
-    let updated_projects = read_projects_from_file(file_path);
-    println!("Updated Projects:");
-    match updated_projects {
-        Ok(updated_projects) => {
-            for project in updated_projects {
-                println!(
-                    "Project Name: {}, Status: {:?}, Focus: {}",
-                    project.name, project.status, project.focus
-                );
-            }
-        }
-        Err(e) => {
-            println!("Failed to read projects: {}", e);
-        }
+    if let Command::Compact = args.command {
+        repo.compact()?;
+        println!("Compacted the project log");
+        return Ok(());
     }
 
-    Ok(())
+    run(args.command, &repo, &sessions)
 }
@@ -0,0 +1,80 @@
+use crate::session::{total_focus_time, Session};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// type Project = {
+///   name: string
+///   status: 'active' | 'inactive' | 'archived'
+///   focus: number
+/// }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Status {
+    Active,
+    Inactive,
+    Archived,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Project {
+    pub name: String,
+    pub status: Status,
+    pub focus: u8, // 0-100
+}
+
+impl Project {
+    /// Total time actually spent on this project, derived by summing its
+    /// closed focus sessions (a running session doesn't count until it's
+    /// stopped).
+    pub fn total_focus_time(&self, sessions: &[Session]) -> chrono::Duration {
+        let mine: Vec<Session> = sessions
+            .iter()
+            .filter(|s| s.name == self.name)
+            .cloned()
+            .collect();
+        total_focus_time(&mine)
+    }
+}
+
+/// A single mutation recorded in a project log. Events are folded over a
+/// `HashMap<String, Project>` to reconstruct current state, so nothing is
+/// ever rewritten in place.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum ProjectEvent {
+    Created { name: String },
+    StatusChanged { name: String, status: Status },
+    FocusChanged { name: String, focus: u8 },
+    Archived { name: String },
+}
+
+pub fn find_project_by_name<'a>(projects: &'a [Project], query: &str) -> Option<&'a Project> {
+    projects.iter().find(|p| p.name == query)
+}
+
+pub fn apply_event(state: &mut HashMap<String, Project>, event: ProjectEvent) {
+    match event {
+        ProjectEvent::Created { name } => {
+            state.entry(name.clone()).or_insert(Project {
+                name,
+                status: Status::Active,
+                focus: 0,
+            });
+        }
+        ProjectEvent::StatusChanged { name, status } => {
+            if let Some(project) = state.get_mut(&name) {
+                project.status = status;
+            }
+        }
+        ProjectEvent::FocusChanged { name, focus } => {
+            if let Some(project) = state.get_mut(&name) {
+                project.focus = focus;
+            }
+        }
+        ProjectEvent::Archived { name } => {
+            if let Some(project) = state.get_mut(&name) {
+                project.status = Status::Archived;
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::project::{apply_event, find_project_by_name, Project, ProjectEvent};
+use anyhow::Result;
+#[cfg(test)]
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+/// Storage for projects, decoupled from the event-log/JSON details of any
+/// particular backend so commands can be tested without touching disk.
+///
+/// `remove` is a soft archive, not a deletion: afterwards the project still
+/// appears in `all()`/`find_by_name` with `status = Archived`. Every
+/// implementation must honor this — callers that want archived projects
+/// hidden filter on `status` themselves.
+pub trait Repository {
+    fn find_by_name(&self, name: &str) -> Result<Option<Project>>;
+    fn all(&self) -> Result<Vec<Project>>;
+    fn upsert(&self, project: Project) -> Result<()>;
+    fn remove(&self, name: &str) -> Result<()>;
+}
+
+/// Repository backed by an append-only `ProjectEvent` log on disk. Current
+/// state is reconstructed by replaying the log from the start; a missing
+/// file is treated as an empty log and malformed lines are skipped (and
+/// reported) rather than aborting the whole replay.
+pub struct FsRepository {
+    path: PathBuf,
+}
+
+impl FsRepository {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn append_event(&self, event: &ProjectEvent) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        Ok(())
+    }
+
+    /// Folds the log down to its current state and rewrites it as a fresh,
+    /// minimal log (one `Created` plus the latest `StatusChanged`/
+    /// `FocusChanged` per project). History of intermediate changes is
+    /// discarded; this is an explicit, opt-in operation, never implicit.
+    pub fn compact(&self) -> Result<()> {
+        let projects = self.all()?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        for project in &projects {
+            writeln!(
+                file,
+                "{}",
+                serde_json::to_string(&ProjectEvent::Created {
+                    name: project.name.clone()
+                })?
+            )?;
+            writeln!(
+                file,
+                "{}",
+                serde_json::to_string(&ProjectEvent::StatusChanged {
+                    name: project.name.clone(),
+                    status: project.status,
+                })?
+            )?;
+            writeln!(
+                file,
+                "{}",
+                serde_json::to_string(&ProjectEvent::FocusChanged {
+                    name: project.name.clone(),
+                    focus: project.focus,
+                })?
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Repository for FsRepository {
+    fn find_by_name(&self, name: &str) -> Result<Option<Project>> {
+        Ok(find_project_by_name(&self.all()?, name).cloned())
+    }
+
+    fn all(&self) -> Result<Vec<Project>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let reader = BufReader::new(file);
+
+        let mut state: HashMap<String, Project> = HashMap::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ProjectEvent>(&line) {
+                Ok(event) => apply_event(&mut state, event),
+                Err(e) => {
+                    eprintln!("{}:{}: skipping malformed event ({e})", self.path.display(), i + 1)
+                }
+            }
+        }
+
+        Ok(state.into_values().collect())
+    }
+
+    fn upsert(&self, project: Project) -> Result<()> {
+        if self.find_by_name(&project.name)?.is_none() {
+            self.append_event(&ProjectEvent::Created {
+                name: project.name.clone(),
+            })?;
+        }
+        self.append_event(&ProjectEvent::StatusChanged {
+            name: project.name.clone(),
+            status: project.status,
+        })?;
+        self.append_event(&ProjectEvent::FocusChanged {
+            name: project.name.clone(),
+            focus: project.focus,
+        })?;
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        // The log is append-only, so removal is recorded as an `Archived`
+        // event rather than erasing history.
+        self.append_event(&ProjectEvent::Archived {
+            name: name.to_string(),
+        })
+    }
+}
+
+/// In-memory repository for tests: no event log, just the folded state.
+/// Test-only, so it doesn't ship in the binary.
+#[cfg(test)]
+pub struct MemoryRepository {
+    projects: RefCell<HashMap<String, Project>>,
+}
+
+#[cfg(test)]
+impl MemoryRepository {
+    pub fn new() -> Self {
+        Self {
+            projects: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Default for MemoryRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+impl Repository for MemoryRepository {
+    fn find_by_name(&self, name: &str) -> Result<Option<Project>> {
+        Ok(self.projects.borrow().get(name).cloned())
+    }
+
+    fn all(&self) -> Result<Vec<Project>> {
+        Ok(self.projects.borrow().values().cloned().collect())
+    }
+
+    fn upsert(&self, project: Project) -> Result<()> {
+        self.projects
+            .borrow_mut()
+            .insert(project.name.clone(), project);
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        if let Some(project) = self.projects.borrow_mut().get_mut(name) {
+            project.status = crate::project::Status::Archived;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::Status;
+
+    #[test]
+    fn memory_repository_upsert_and_find() {
+        let repo = MemoryRepository::default();
+        repo.upsert(Project {
+            name: "one".to_string(),
+            status: Status::Active,
+            focus: 50,
+        })
+        .unwrap();
+
+        assert_eq!(repo.find_by_name("one").unwrap().unwrap().focus, 50);
+        assert!(repo.find_by_name("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn memory_repository_remove_is_a_soft_archive() {
+        let repo = MemoryRepository::default();
+        repo.upsert(Project {
+            name: "one".to_string(),
+            status: Status::Active,
+            focus: 50,
+        })
+        .unwrap();
+
+        repo.remove("one").unwrap();
+
+        let project = repo.find_by_name("one").unwrap().unwrap();
+        assert_eq!(project.status, Status::Archived);
+        assert_eq!(repo.all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn fs_repository_remove_matches_memory_repository_contract() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let repo = FsRepository::new(file.path());
+        repo.upsert(Project {
+            name: "one".to_string(),
+            status: Status::Active,
+            focus: 50,
+        })
+        .unwrap();
+
+        repo.remove("one").unwrap();
+
+        let project = repo.find_by_name("one").unwrap().unwrap();
+        assert_eq!(project.status, Status::Archived);
+        assert_eq!(repo.all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn fs_repository_compact_preserves_current_state() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let repo = FsRepository::new(file.path());
+        repo.upsert(Project {
+            name: "one".to_string(),
+            status: Status::Active,
+            focus: 10,
+        })
+        .unwrap();
+        repo.upsert(Project {
+            name: "one".to_string(),
+            status: Status::Active,
+            focus: 90,
+        })
+        .unwrap();
+
+        repo.compact().unwrap();
+
+        let projects = repo.all().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].focus, 90);
+    }
+
+    #[test]
+    fn fs_repository_skips_malformed_lines() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "not json").unwrap();
+        writeln!(
+            file,
+            "{}",
+            serde_json::to_string(&ProjectEvent::Created {
+                name: "one".to_string()
+            })
+            .unwrap()
+        )
+        .unwrap();
+
+        let repo = FsRepository::new(file.path());
+        assert_eq!(repo.all().unwrap().len(), 1);
+    }
+}
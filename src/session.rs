@@ -0,0 +1,186 @@
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// A single focus session. Stored as an append-only log alongside
+/// projects: starting a session appends a record with `ended_at: None`;
+/// stopping it appends another record for the same `(name, started_at)`
+/// key with `ended_at` set, closing it without rewriting history.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Session {
+    pub name: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    pub fn duration(&self) -> Duration {
+        self.ended_at.unwrap_or_else(Utc::now) - self.started_at
+    }
+}
+
+/// Sums the duration of every closed session, ignoring any still running.
+pub fn total_focus_time(sessions: &[Session]) -> Duration {
+    sessions
+        .iter()
+        .filter(|s| s.ended_at.is_some())
+        .map(Session::duration)
+        .fold(Duration::zero(), |acc, d| acc + d)
+}
+
+/// Renders a `chrono::Duration` as `HhMMmSSs`, matching the precision the
+/// `status`/`stop` commands need without pulling in a formatting crate.
+pub fn format_duration(d: Duration) -> String {
+    let total_seconds = d.num_seconds().max(0);
+    format!(
+        "{}h{:02}m{:02}s",
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60
+    )
+}
+
+/// Derives the sessions log path from the project data path, e.g.
+/// `act.jsonl` -> `act-sessions.jsonl`, so sessions live next to projects
+/// without needing their own config entry.
+pub fn derive_sessions_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "act".to_string());
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_else(|| "jsonl".to_string());
+    path.with_file_name(format!("{stem}-sessions.{extension}"))
+}
+
+pub struct SessionStore {
+    path: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn append(&self, session: &Session) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(session)?)?;
+        Ok(())
+    }
+
+    /// Folds the log down to the latest record per `(name, started_at)`.
+    pub fn all(&self) -> Result<Vec<Session>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let reader = BufReader::new(file);
+
+        let mut state: HashMap<(String, DateTime<Utc>), Session> = HashMap::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Session>(&line) {
+                Ok(session) => {
+                    state.insert((session.name.clone(), session.started_at), session);
+                }
+                Err(e) => eprintln!(
+                    "{}:{}: skipping malformed session ({e})",
+                    self.path.display(),
+                    i + 1
+                ),
+            }
+        }
+        Ok(state.into_values().collect())
+    }
+
+    pub fn sessions_for(&self, name: &str) -> Result<Vec<Session>> {
+        Ok(self.all()?.into_iter().filter(|s| s.name == name).collect())
+    }
+
+    /// The currently active session, if any. At most one session may be
+    /// open (`ended_at: None`) at a time.
+    pub fn current(&self) -> Result<Option<Session>> {
+        Ok(self.all()?.into_iter().find(|s| s.ended_at.is_none()))
+    }
+
+    /// Starts a session for `name`. Errors if another session is already
+    /// running unless `switch` is set, in which case the prior session is
+    /// stopped first.
+    pub fn start(&self, name: &str, switch: bool) -> Result<()> {
+        if let Some(current) = self.current()? {
+            if current.name == name {
+                bail!("'{name}' is already the active session");
+            }
+            if !switch {
+                bail!(
+                    "'{}' is already active; pass --switch to stop it and start '{name}'",
+                    current.name
+                );
+            }
+            self.stop()?;
+        }
+        self.append(&Session {
+            name: name.to_string(),
+            started_at: Utc::now(),
+            ended_at: None,
+        })
+    }
+
+    /// Closes the active session, recording its end time.
+    pub fn stop(&self) -> Result<Option<Session>> {
+        let Some(mut current) = self.current()? else {
+            return Ok(None);
+        };
+        current.ended_at = Some(Utc::now());
+        self.append(&current)?;
+        Ok(Some(current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_stop_and_sessions_for() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let store = SessionStore::new(file.path());
+
+        store.start("one", false).unwrap();
+        assert_eq!(store.current().unwrap().unwrap().name, "one");
+
+        let stopped = store.stop().unwrap().unwrap();
+        assert_eq!(stopped.name, "one");
+        assert!(stopped.ended_at.is_some());
+        assert!(store.current().unwrap().is_none());
+
+        let sessions = store.sessions_for("one").unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert!(total_focus_time(&sessions) >= Duration::zero());
+    }
+
+    #[test]
+    fn start_refuses_a_second_project_without_switch() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let store = SessionStore::new(file.path());
+
+        store.start("one", false).unwrap();
+        assert!(store.start("two", false).is_err());
+
+        store.start("two", true).unwrap();
+        assert_eq!(store.current().unwrap().unwrap().name, "two");
+    }
+}